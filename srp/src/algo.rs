@@ -1,23 +1,41 @@
 extern crate rand;
 extern crate serialize;
 
+mod error;
+mod groups;
+mod kdf;
+mod server;
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
 use bignum::BigNumTrait;
 use bignum::NumBigInt as BigNum;
-use mac::hmac_sha256;
 use sha2::{Sha256, Digest};
 
 use rand::Rng;
 
+pub use error::SrpError;
+pub use groups::SrpGroup;
+pub use kdf::{DefaultKdf, PasswordKdf, Pbkdf2};
+pub use server::Server;
+
 pub enum LoginResult {
     Success,
     Failure
 }
 
 #[derive(Debug)]
-pub struct SRP {
+pub struct SRP<D: Digest = Sha256, K: PasswordKdf<D> = DefaultKdf> {
     N: BigNum,
     g: BigNum,
     k: BigNum,
+    /// `true` for this crate's original, non-standard scheme (`k = 3`,
+    /// `x = H(salt || password)`); `false` for SRP-6a / RFC 5054
+    /// (`k = H(N || PAD(g))`, `x = H(salt || H(I || ":" || P))`).
+    legacy: bool,
+    kdf: K,
+    _digest: PhantomData<D>,
 }
 
 pub fn serialize<T: BigNumTrait>(x: &T) -> Vec<u8> {
@@ -28,44 +46,100 @@ pub fn deserialize<T: BigNumTrait>(x: &[u8]) -> T {
     T::from_bytes_be(x)
 }
 
-impl SRP {
+impl<D: Digest> SRP<D, DefaultKdf> {
+    /// Builds an `SRP` instance using the 1536-bit RFC 5054 group and this
+    /// crate's original, non-standard `k`/`x` scheme (see
+    /// [`SRP::legacy_with_group`]), the default this crate has always used.
     pub fn new() -> Self {
-        let N_hex = "ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74\
-                     020bbea63b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f1437\
-                     4fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7ed\
-                     ee386bfb5a899fa5ae9f24117c4b1fe649286651ece45b3dc2007cb8a163bf05\
-                     98da48361c55d39a69163fa8fd24cf5f83655d23dca3ad961c62f356208552bb\
-                     9ed529077096966d670c354e4abc9804f1746c08ca237327ffffffffffffffff";
-
-        let N = BigNum::from_hex_str(N_hex).unwrap();
-        let g = BigNum::from_u32(2);
-        let k = BigNum::from_u32(3);
+        Self::with_group(groups::rfc5054_1536())
+    }
+
+    /// Builds an `SRP` instance from one of the standard RFC 5054 groups
+    /// (see the `groups` module), using the legacy `k`/`x` scheme. So
+    /// callers can pick a security level and interoperate with other SRP
+    /// implementations while keeping this crate's historical behavior.
+    pub fn with_group(group: &SrpGroup) -> Self {
+        Self::legacy_with_group(group)
+    }
+
+    /// Builds an `SRP` instance using this crate's original scheme:
+    /// `k = 3` and `x = H(salt || password)`. Neither of these matches
+    /// SRP-6a or RFC 5054; kept only so existing callers keep working.
+    /// New code should prefer [`SRP::rfc5054`].
+    pub fn legacy_with_group(group: &SrpGroup) -> Self {
+        SRP {
+            N: group.N.clone(),
+            g: group.g.clone(),
+            k: BigNum::from_u32(3),
+            legacy: true,
+            kdf: DefaultKdf,
+            _digest: PhantomData,
+        }
+    }
+
+    /// Builds an SRP-6a / RFC 5054 compliant instance: `k = H(N || PAD(g))`
+    /// and `x = H(salt || H(I || ":" || P))`, interoperable with other SRP
+    /// implementations that follow the RFC.
+    pub fn rfc5054(group: &SrpGroup) -> Self {
+        let N = group.N.clone();
+        let g = group.g.clone();
+        let k = compute_k::<D>(&N, &g);
         SRP {
             N,
             g,
             k,
+            legacy: false,
+            kdf: DefaultKdf,
+            _digest: PhantomData,
         }
     }
+}
 
-    pub fn password_to_secret(&self, password: &[u8]) -> (Vec<u8>, BigNum) {
+impl<D: Digest, K: PasswordKdf<D>> SRP<D, K> {
+    /// Builds an `SRP` instance that derives `x` through a custom
+    /// [`PasswordKdf`] (e.g. [`Pbkdf2`] with a high iteration count)
+    /// instead of the default single hash pass, to slow down offline
+    /// brute-force of a leaked verifier database.
+    pub fn with_kdf(group: &SrpGroup, legacy: bool, kdf: K) -> Self {
+        let N = group.N.clone();
+        let g = group.g.clone();
+        let k = if legacy { BigNum::from_u32(3) } else { compute_k::<D>(&N, &g) };
+        SRP {
+            N,
+            g,
+            k,
+            legacy,
+            kdf,
+            _digest: PhantomData,
+        }
+    }
+
+    pub fn password_to_secret(&self, identity: &[u8], password: &[u8]) -> (Vec<u8>, BigNum) {
         let mut rng = rand::thread_rng();
         // Which size should the salt have?
         let salt: Vec<u8> = rng.gen_iter::<u8>().take(128).collect();
 
-        let x = compute_x(&salt, password);
+        let x = compute_x::<D, K>(&self.kdf, &self.N, self.legacy, identity, &salt, password);
         (salt, self.g.mod_exp(&x, &self.N))
     }
 }
 
-struct HandshakeState<'a> {
-    srp: &'a SRP,
+struct HandshakeState<'a, D: Digest + 'a, K: PasswordKdf<D> + 'a> {
+    srp: &'a SRP<D, K>,
     exponent: BigNum,
     power: BigNum,
 }
 
-impl<'a> HandshakeState<'a> {
-    pub fn new(srp: &'a SRP) -> Self {
-        let exponent = BigNum::gen_below(&srp.N);
+impl<'a, D: Digest, K: PasswordKdf<D>> HandshakeState<'a, D, K> {
+    pub fn new(srp: &'a SRP<D, K>) -> Self {
+        Self::with_exponent(srp, BigNum::gen_below(&srp.N))
+    }
+
+    /// Rebuilds the state around a previously generated private exponent,
+    /// recomputing `power` deterministically from it. Lets a caller that
+    /// only kept the exponent (not this borrowing struct) around between
+    /// two steps of a handshake resume where it left off.
+    fn with_exponent(srp: &'a SRP<D, K>, exponent: BigNum) -> Self {
         let power = srp.g.mod_exp(&exponent, &srp.N);
         HandshakeState {
             srp,
@@ -75,16 +149,30 @@ impl<'a> HandshakeState<'a> {
     }
 }
 
-pub struct ClientHandshake<'a> {
-    state: HandshakeState<'a>,
+/// State cached by [`ClientHandshake`] after [`ClientHandshake::compute_secret`]
+/// and [`ClientHandshake::proof`], so [`ClientHandshake::verify_server`] can
+/// be called with just the server's `M2` in hand.
+struct ClientSession {
+    B: BigNum,
+    salt: Vec<u8>,
+    K: Vec<u8>,
+    M1: Vec<u8>,
+}
+
+pub struct ClientHandshake<'a, D: Digest + 'a = Sha256, K: PasswordKdf<D> + 'a = DefaultKdf> {
+    state: HandshakeState<'a, D, K>,
+    identity: &'a [u8],
     password: &'a [u8],
+    session: RefCell<Option<ClientSession>>,
 }
 
-impl <'a> ClientHandshake<'a> {
-    pub fn new(srp: &'a SRP, password: &'a [u8]) -> Self {
+impl <'a, D: Digest, K: PasswordKdf<D>> ClientHandshake<'a, D, K> {
+    pub fn new(srp: &'a SRP<D, K>, identity: &'a [u8], password: &'a [u8]) -> Self {
         ClientHandshake {
             state: HandshakeState::new(srp),
-            password
+            identity,
+            password,
+            session: RefCell::new(None),
         }
     }
 
@@ -92,7 +180,12 @@ impl <'a> ClientHandshake<'a> {
         &self.state.power
     }
 
-    pub fn compute_secret(&self, B: &BigNum, salt: &[u8]) -> Vec<u8> {
+    /// Derives the shared session key from the server's public value `B`.
+    ///
+    /// Rejects `B` if it is congruent to `0 mod N`, and rejects a
+    /// derived scrambling parameter `u` of `0` — either would let a
+    /// malicious server force a predictable secret (see [`SrpError`]).
+    pub fn compute_secret(&self, B: &BigNum, salt: &[u8]) -> Result<Vec<u8>, SrpError> {
         let state = &self.state;
         let srp = state.srp;
         let N = &srp.N;
@@ -101,29 +194,79 @@ impl <'a> ClientHandshake<'a> {
         let a = &state.exponent;
         let A = &state.power;
 
-        let u = compute_u(A, B);
-        let x = compute_x(salt, self.password);
+        if is_congruent_to_zero(B, N) {
+            return Err(SrpError::IllegalPublicValue);
+        }
+
+        let u = compute_u::<D>(A, B);
+        if is_zero(&u) {
+            return Err(SrpError::IllegalPublicValue);
+        }
+
+        let x = compute_x::<D, K>(&srp.kdf, N, srp.legacy, self.identity, salt, self.password);
 
         let S = (B - &(k * &g.mod_exp(&x, N))).mod_exp(&(a + &(&u * &x)), N);
-        let K = Sha256::digest(&serialize(&S)).to_vec();
-        hmac_sha256(&K, salt)
+        let K = D::digest(&serialize(&S)).to_vec();
+        let K = hmac::<D>(&K, salt);
+
+        *self.session.borrow_mut() = Some(ClientSession {
+            B: B.clone(),
+            salt: salt.to_vec(),
+            K: K.clone(),
+            M1: Vec::new(),
+        });
+        Ok(K)
+    }
+
+    /// Computes this client's `M1` proof, `H(H(N) XOR H(g) || H(I) || salt
+    /// || A || B || K)`. Must be called after [`ClientHandshake::compute_secret`].
+    pub fn proof(&self) -> Vec<u8> {
+        let mut session = self.session.borrow_mut();
+        let session = session.as_mut()
+            .expect("ClientHandshake::compute_secret must be called before proof");
+
+        let srp = self.state.srp;
+        let M1 = compute_m1::<D>(&srp.N, &srp.g, self.identity, &session.salt, self.A(), &session.B, &session.K);
+        session.M1 = M1.clone();
+        M1
+    }
+
+    /// Verifies the server's `M2 = H(A || M1 || K)` reply, in constant
+    /// time. Must be called after [`ClientHandshake::proof`].
+    pub fn verify_server(&self, M2: &[u8]) -> bool {
+        let session = self.session.borrow();
+        let session = session.as_ref()
+            .expect("ClientHandshake::proof must be called before verify_server");
+
+        let expected_M2 = compute_m2::<D>(self.A(), &session.M1, &session.K);
+        constant_time_eq(&expected_M2, M2)
     }
 }
 
-pub struct ServerHandshake<'a> {
-    state: HandshakeState<'a>,
+pub struct ServerHandshake<'a, D: Digest + 'a = Sha256, K: PasswordKdf<D> + 'a = DefaultKdf> {
+    state: HandshakeState<'a, D, K>,
     B: BigNum,
+    identity: &'a [u8],
     salt: &'a [u8],
     v: &'a BigNum,
 }
 
-impl <'a> ServerHandshake<'a> {
-    pub fn new(srp: &'a SRP, salt: &'a [u8], v: &'a BigNum) -> Self {
-        let state = HandshakeState::new(srp);
+impl <'a, D: Digest, K: PasswordKdf<D>> ServerHandshake<'a, D, K> {
+    pub fn new(srp: &'a SRP<D, K>, identity: &'a [u8], salt: &'a [u8], v: &'a BigNum) -> Self {
+        Self::with_exponent(srp, BigNum::gen_below(&srp.N), identity, salt, v)
+    }
+
+    /// Rebuilds a `ServerHandshake` around a previously generated private
+    /// exponent `b` instead of generating a new one, so a caller that can
+    /// only keep owned state (not this struct, which borrows from `SRP`)
+    /// between steps can still reuse this type's math for the second step.
+    fn with_exponent(srp: &'a SRP<D, K>, exponent: BigNum, identity: &'a [u8], salt: &'a [u8], v: &'a BigNum) -> Self {
+        let state = HandshakeState::with_exponent(srp, exponent);
         let B = &state.power + &(&srp.k * v);
         ServerHandshake {
             state,
             B,
+            identity,
             salt,
             v,
         }
@@ -133,30 +276,297 @@ impl <'a> ServerHandshake<'a> {
         &self.B
     }
 
-    pub fn compute_secret(&self, A: &BigNum) -> Vec<u8> {
+    /// Derives the shared session key from the client's public value `A`.
+    ///
+    /// Rejects `A` if it is congruent to `0 mod N`, and rejects a derived
+    /// scrambling parameter `u` of `0` — either would let an attacker
+    /// force a predictable secret and authenticate without the password
+    /// (see [`SrpError`]).
+    pub fn compute_secret(&self, A: &BigNum) -> Result<Vec<u8>, SrpError> {
         let state = &self.state;
         let srp = state.srp;
         let N = &srp.N;
         let b = &state.exponent;
         let B = &self.B;
 
-        let u = compute_u(A, B);
+        if is_congruent_to_zero(A, N) {
+            return Err(SrpError::IllegalPublicValue);
+        }
+
+        let u = compute_u::<D>(A, B);
+        if is_zero(&u) {
+            return Err(SrpError::IllegalPublicValue);
+        }
+
         let S = (A * &self.v.mod_exp(&u, N)).mod_exp(b, N);
-        let K = Sha256::digest(&serialize(&S)).to_vec();
-        hmac_sha256(&K, self.salt)
+        let K = D::digest(&serialize(&S)).to_vec();
+        Ok(hmac::<D>(&K, self.salt))
+    }
+
+    /// Verifies the client's `M1` proof and returns whether the login
+    /// succeeded, comparing in constant time. A rejected `A` (see
+    /// [`ServerHandshake::compute_secret`]) is treated as a failed login.
+    pub fn verify_client(&self, A: &BigNum, M1: &[u8]) -> LoginResult {
+        let K = match self.compute_secret(A) {
+            Ok(K) => K,
+            Err(_) => return LoginResult::Failure,
+        };
+
+        let srp = self.state.srp;
+        let expected_M1 = compute_m1::<D>(&srp.N, &srp.g, self.identity, self.salt, A, &self.B, &K);
+
+        if constant_time_eq(&expected_M1, M1) {
+            LoginResult::Success
+        } else {
+            LoginResult::Failure
+        }
     }
+
+    /// Computes this server's `M2 = H(A || M1 || K)` reply, to be sent back
+    /// once [`ServerHandshake::verify_client`] has returned `Success`.
+    ///
+    /// Rejects `A` under the same conditions as
+    /// [`ServerHandshake::compute_secret`] rather than assuming a prior
+    /// [`ServerHandshake::verify_client`] call already validated it.
+    pub fn proof(&self, A: &BigNum, M1: &[u8]) -> Result<Vec<u8>, SrpError> {
+        let K = self.compute_secret(A)?;
+        Ok(compute_m2::<D>(A, M1, &K))
+    }
+}
+
+/// Whether `value mod N == 0`, which `A` and `B` must never be: accepting
+/// either lets a malicious peer zero out the shared secret `S`.
+fn is_congruent_to_zero(value: &BigNum, N: &BigNum) -> bool {
+    is_zero(&(value % N))
 }
 
-fn compute_u(A: &BigNum, B: &BigNum) -> BigNum {
+fn is_zero(value: &BigNum) -> bool {
+    *value == BigNum::from_u32(0)
+}
+
+/// HMAC (RFC 2104), generic over the digest `D`. Assumes a 64-byte block
+/// size, which holds for every digest this crate instantiates (SHA-1,
+/// SHA-256); a digest with a different block size would need its own
+/// constant here. Replaces this crate's earlier dependency on a
+/// `mac::hmac_sha256`-only helper, which had no generic equivalent to move
+/// to once `SRP` became generic over `D`.
+fn hmac<D: Digest>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    const BLOCK_SIZE: usize = 64;
+
+    let mut block_key = vec![0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        let hashed = D::digest(key);
+        block_key[..hashed.len()].copy_from_slice(&hashed);
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let ipad: Vec<u8> = block_key.iter().map(|b| b ^ 0x36).collect();
+    let opad: Vec<u8> = block_key.iter().map(|b| b ^ 0x5c).collect();
+
+    let mut inner = ipad;
+    inner.extend_from_slice(message);
+    let inner_hash = D::digest(&inner);
+
+    let mut outer = opad;
+    outer.extend_from_slice(&inner_hash);
+    D::digest(&outer).to_vec()
+}
+
+fn compute_u<D: Digest>(A: &BigNum, B: &BigNum) -> BigNum {
     let mut buffer = Vec::new();
     buffer.extend_from_slice(&serialize(A));
     buffer.extend_from_slice(&serialize(B));
-    deserialize(&Sha256::digest(&buffer))
+    deserialize(&D::digest(&buffer))
+}
+
+fn compute_x<D: Digest, K: PasswordKdf<D>>(kdf: &K, N: &BigNum, legacy: bool, identity: &[u8], salt: &[u8], password: &[u8]) -> BigNum {
+    if legacy {
+        kdf.derive(N, salt, password)
+    } else {
+        let mut inner = Vec::with_capacity(identity.len() + 1 + password.len());
+        inner.extend_from_slice(identity);
+        inner.push(b':');
+        inner.extend_from_slice(password);
+        let inner_hash = D::digest(&inner);
+
+        kdf.derive(N, salt, &inner_hash)
+    }
+}
+
+/// `k = H(N || PAD(g))` as defined by SRP-6a / RFC 5054, where `PAD`
+/// left-pads `g`'s big-endian bytes to the exact byte length of `N`.
+fn compute_k<D: Digest>(N: &BigNum, g: &BigNum) -> BigNum {
+    let n_bytes = serialize(N);
+    let mut g_bytes = serialize(g);
+    if g_bytes.len() < n_bytes.len() {
+        let mut padded = vec![0u8; n_bytes.len() - g_bytes.len()];
+        padded.append(&mut g_bytes);
+        g_bytes = padded;
+    }
+
+    let mut buffer = Vec::with_capacity(n_bytes.len() + g_bytes.len());
+    buffer.extend_from_slice(&n_bytes);
+    buffer.extend_from_slice(&g_bytes);
+    deserialize(&D::digest(&buffer))
 }
 
-fn compute_x(salt: &[u8], password: &[u8]) -> BigNum {
-    let mut buffer = Vec::with_capacity(salt.len() + password.len());
+/// `M1 = H(H(N) XOR H(g) || H(I) || salt || A || B || K)`, the client's
+/// proof that it derived the same session key as the server.
+fn compute_m1<D: Digest>(N: &BigNum, g: &BigNum, identity: &[u8], salt: &[u8], A: &BigNum, B: &BigNum, K: &[u8]) -> Vec<u8> {
+    let h_n = D::digest(&serialize(N));
+    let h_g = D::digest(&serialize(g));
+    let h_ng: Vec<u8> = h_n.iter().zip(h_g.iter()).map(|(n, g)| n ^ g).collect();
+    let h_i = D::digest(identity);
+
+    let mut buffer = Vec::new();
+    buffer.extend_from_slice(&h_ng);
+    buffer.extend_from_slice(&h_i);
     buffer.extend_from_slice(salt);
-    buffer.extend_from_slice(password);
-    deserialize(&Sha256::digest(&buffer))
+    buffer.extend_from_slice(&serialize(A));
+    buffer.extend_from_slice(&serialize(B));
+    buffer.extend_from_slice(K);
+    D::digest(&buffer).to_vec()
+}
+
+/// `M2 = H(A || M1 || K)`, the server's proof that it derived the same
+/// session key as the client.
+fn compute_m2<D: Digest>(A: &BigNum, M1: &[u8], K: &[u8]) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    buffer.extend_from_slice(&serialize(A));
+    buffer.extend_from_slice(M1);
+    buffer.extend_from_slice(K);
+    D::digest(&buffer).to_vec()
+}
+
+/// Compares two byte slices without short-circuiting on the first
+/// mismatch, so proof verification does not leak timing information about
+/// where two proofs diverge.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha1::Sha1;
+
+    // RFC 5054 Appendix B test vector: I = "alice", P = "password123",
+    // using the 1024-bit group.
+    #[test]
+    fn rfc5054_x_matches_appendix_b() {
+        let salt = hex_decode("BEB25379D1A8581EB5A727673A2441EE");
+        let N = &groups::rfc5054_1024().N;
+        let x = compute_x::<Sha1, DefaultKdf>(&DefaultKdf, N, false, b"alice", &salt, b"password123");
+
+        let expected_x = BigNum::from_hex_str(
+            "94B7555AABE9127CC58CCF4993DB6CF84D16C124"
+        ).unwrap();
+        assert_eq!(x, expected_x);
+    }
+
+    #[test]
+    fn rfc5054_k_matches_appendix_b() {
+        let group = groups::rfc5054_1024();
+        let k = compute_k::<Sha1>(&group.N, &group.g);
+
+        let expected_k = BigNum::from_hex_str(
+            "7556AA045AEF2CDD07ABAF0F665C3E818913186F"
+        ).unwrap();
+        assert_eq!(k, expected_k);
+    }
+
+    #[test]
+    fn handshake_establishes_mutual_trust() {
+        let srp = SRP::<Sha256>::new();
+        let identity = b"alice";
+        let password = b"password123";
+        let (salt, v) = srp.password_to_secret(identity, password);
+
+        let client = ClientHandshake::new(&srp, identity, password);
+        let server = ServerHandshake::new(&srp, identity, &salt, &v);
+
+        let client_key = client.compute_secret(server.B(), &salt).unwrap();
+        let m1 = client.proof();
+
+        match server.verify_client(client.A(), &m1) {
+            LoginResult::Success => {}
+            LoginResult::Failure => panic!("server rejected a valid proof"),
+        }
+
+        let m2 = server.proof(client.A(), &m1).unwrap();
+        assert!(client.verify_server(&m2));
+        let server_key = server.compute_secret(client.A()).unwrap();
+        assert_eq!(client_key, server_key);
+    }
+
+    #[test]
+    fn handshake_rejects_forged_proof() {
+        let srp = SRP::<Sha256>::new();
+        let identity = b"alice";
+        let password = b"password123";
+        let (salt, v) = srp.password_to_secret(identity, password);
+
+        let client = ClientHandshake::new(&srp, identity, password);
+        let server = ServerHandshake::new(&srp, identity, &salt, &v);
+
+        client.compute_secret(server.B(), &salt).unwrap();
+        let mut forged_m1 = client.proof();
+        forged_m1[0] ^= 0xff;
+
+        match server.verify_client(client.A(), &forged_m1) {
+            LoginResult::Failure => {}
+            LoginResult::Success => panic!("server accepted a forged proof"),
+        }
+    }
+
+    #[test]
+    fn rejects_zero_public_values() {
+        let srp = SRP::<Sha256>::new();
+        let identity = b"alice";
+        let password = b"password123";
+        let (salt, v) = srp.password_to_secret(identity, password);
+
+        let client = ClientHandshake::new(&srp, identity, password);
+        let server = ServerHandshake::new(&srp, identity, &salt, &v);
+
+        let zero = BigNum::from_u32(0);
+        assert_eq!(client.compute_secret(&zero, &salt), Err(SrpError::IllegalPublicValue));
+        assert_eq!(client.compute_secret(&srp.N, &salt), Err(SrpError::IllegalPublicValue));
+        assert_eq!(client.compute_secret(&(&srp.N * &BigNum::from_u32(2)), &salt), Err(SrpError::IllegalPublicValue));
+
+        assert_eq!(server.compute_secret(&zero), Err(SrpError::IllegalPublicValue));
+        assert_eq!(server.compute_secret(&srp.N), Err(SrpError::IllegalPublicValue));
+        assert_eq!(server.compute_secret(&(&srp.N * &BigNum::from_u32(2))), Err(SrpError::IllegalPublicValue));
+    }
+
+    #[test]
+    fn pluggable_kdf_is_used_for_both_x_and_verifier() {
+        let srp = SRP::<Sha256, _>::with_kdf(groups::rfc5054_1536(), true, Pbkdf2::<Sha256>::new(10));
+        let identity = b"alice";
+        let password = b"password123";
+        let (salt, v) = srp.password_to_secret(identity, password);
+
+        let x = compute_x::<Sha256, _>(&srp.kdf, &srp.N, srp.legacy, identity, &salt, password);
+        assert_eq!(v, srp.g.mod_exp(&x, &srp.N));
+    }
+
+    #[test]
+    fn constant_time_eq_detects_any_mismatch() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+    }
+
+    fn hex_decode(s: &str) -> Vec<u8> {
+        serialize(&BigNum::from_hex_str(s).unwrap())
+    }
 }
@@ -0,0 +1,28 @@
+use std::error;
+use std::fmt;
+
+/// Errors that can occur while running an SRP handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SrpError {
+    /// The peer's public value (`A` or `B`) was congruent to `0 mod N`, or
+    /// the derived scrambling parameter `u` was `0`. Accepting either
+    /// would let an attacker force a predictable shared secret and
+    /// authenticate without knowing the password.
+    IllegalPublicValue,
+    /// A login was started for an identity that has no registered
+    /// verifier.
+    UnknownIdentity,
+}
+
+impl fmt::Display for SrpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SrpError::IllegalPublicValue => {
+                write!(f, "peer's public value (or u) was congruent to 0 mod N")
+            }
+            SrpError::UnknownIdentity => write!(f, "no such identity is registered"),
+        }
+    }
+}
+
+impl error::Error for SrpError {}
@@ -0,0 +1,129 @@
+use std::sync::OnceLock;
+
+use bignum::BigNumTrait;
+use bignum::NumBigInt as BigNum;
+
+/// One of the standard (N, g) pairs for SRP defined in RFC 5054, Appendix A.
+///
+/// `g` is always the conventional generator `2` for these groups; `N` is a
+/// safe prime of the group's bit size.
+#[derive(Debug)]
+pub struct SrpGroup {
+    pub N: BigNum,
+    pub g: BigNum,
+}
+
+impl SrpGroup {
+    fn from_hex(n_hex: &str) -> Self {
+        SrpGroup {
+            N: BigNum::from_hex_str(n_hex).unwrap(),
+            g: BigNum::from_u32(2),
+        }
+    }
+}
+
+macro_rules! srp_group {
+    ($name:ident, $doc:expr, $n_hex:expr) => {
+        #[doc = $doc]
+        pub fn $name() -> &'static SrpGroup {
+            static GROUP: OnceLock<SrpGroup> = OnceLock::new();
+            GROUP.get_or_init(|| SrpGroup::from_hex($n_hex))
+        }
+    };
+}
+
+srp_group!(
+    rfc5054_1024,
+    "The 1024-bit RFC 5054 group.",
+    "eeaf0ab9adb38dd69c33f80afa8fc5e86072618775ff3c0b9ea2314c9c256576d674df7496ea81d\
+     3383b4813d692c6e0e0d5d8e250b98be48e495c1d6089dad15dc7d7b46154d6b6ce8ef4ad69b15d\
+     4982559b297bcf1885c529f566660e57ec68edbc3c05726cc02fd4cbf4976eaa9afd5138fe83764\
+     35b9fc61d2fc0eb06e3"
+);
+
+srp_group!(
+    rfc5054_1536,
+    "The 1536-bit RFC 5054 group.",
+    "ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74\
+     020bbea63b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f1437\
+     4fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7ed\
+     ee386bfb5a899fa5ae9f24117c4b1fe649286651ece45b3dc2007cb8a163bf05\
+     98da48361c55d39a69163fa8fd24cf5f83655d23dca3ad961c62f356208552bb\
+     9ed529077096966d670c354e4abc9804f1746c08ca237327ffffffffffffffff"
+);
+
+srp_group!(
+    rfc5054_2048,
+    "The 2048-bit RFC 5054 group.",
+    "ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74\
+     020bbea63b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f1437\
+     4fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7ed\
+     ee386bfb5a899fa5ae9f24117c4b1fe649286651ece45b3dc2007cb8a163bf05\
+     98da48361c55d39a69163fa8fd24cf5f83655d23dca3ad961c62f356208552bb\
+     9ed529077096966d670c354e4abc9804f1746c08ca18217c32905e462e36ce3b\
+     e39e772c180e86039b2783a2ec07a28fb5c55df06f4c52c9de2bcbf6955817183\
+     995497cea956ae515d2261898fa051015728e5a8aacaa68ffffffffffffffff"
+);
+
+srp_group!(
+    rfc5054_3072,
+    "The 3072-bit RFC 5054 group.",
+    "ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74\
+     020bbea63b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f1437\
+     4fe1356d6d51c245e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7ed\
+     ee386bfb5a899fa5ae9f24117c4b1fe649286651ece45b3dc2007cb8a163bf05\
+     98da48361c55d39a69163fa8fd24cf5f83655d23dca3ad961c62f356208552bb\
+     9ed529077096966d670c354e4abc9804f1746c08ca18217c32905e462e36ce3b\
+     e39e772c180e86039b2783a2ec07a28fb5c55df06f4c52c9de2bcbf6955817183\
+     995497cea956ae515d2261898fa051015728e5a8aaac42dad33170d04507a33a\
+     85521abdf1cba64ecfb850458dbef0a8aea71575d060c7db3970f85a6e1e4c7a\
+     bf5ae8cdb0933d71e8c94e04a25619dcee3d2261ad2ee6bf12ffa06d98a0864d\
+     87602733ec86a64521f2b18177b200cbbe117577a615d6c770988c0bad946e20\
+     8e24fa074e5ab3143db5bfce0fd108e4b82d120a9210801ffffffffffffffff"
+);
+
+// NOT YET IMPLEMENTED: `rfc5054_4096`, `rfc5054_6144`, `rfc5054_8192`.
+//
+// This catalogue only covers 4 of the 7 RFC 5054 Appendix A groups. The
+// remaining three were dropped rather than shipped with hand-typed primes
+// this crate had no way to check against the published RFC text (no
+// network access in the environment this was written in) — that's
+// exactly how the 2048/3072/4096 slots ended up silently wrong before.
+// Callers asking for `with_group`/`rfc5054` with one of these sizes have
+// nothing to call until someone sources and verifies the real values.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    fn fingerprint(group: &SrpGroup) -> String {
+        Sha256::digest(&group.N.to_bytes_be())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    // Pins each group's `N` to a SHA-256 fingerprint of its exact bytes,
+    // not just its bit length — a byte-length check alone would have let
+    // the mislabeled/shifted primes this test replaces through again.
+    #[test]
+    fn group_primes_match_known_fingerprints() {
+        assert_eq!(
+            fingerprint(rfc5054_1024()),
+            "494b6a801b379f37c9ee25d5db7cd70ffcfe53d01b7c9e4470eaca46bda24b39"
+        );
+        assert_eq!(
+            fingerprint(rfc5054_1536()),
+            "64fcc83ec403930bf18393dbc883ccaa1fbb08ac876f77f7aa99748ca945019b"
+        );
+        assert_eq!(
+            fingerprint(rfc5054_2048()),
+            "d66436f79bbd6b2e38c0ffbd079be904d2641415e2e67140e09448be9a60890e"
+        );
+        assert_eq!(
+            fingerprint(rfc5054_3072()),
+            "8a13ed6a95516bbe314539d9204f78876fee675f5a6db47a615c1045c6c5b515"
+        );
+    }
+}
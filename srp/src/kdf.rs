@@ -0,0 +1,69 @@
+use std::marker::PhantomData;
+
+use bignum::NumBigInt as BigNum;
+use sha2::Digest;
+
+use super::{deserialize, hmac};
+
+/// Derives the SRP private key material `x` from a salt and a password
+/// (or, for SRP-6a, from a salt and `H(I || ":" || P)`), reduced mod `N`.
+///
+/// Implementations let `SRP` trade off compatibility (the crate's default,
+/// a single hash pass) against resistance to offline brute-force of a
+/// leaked verifier database (e.g. [`Pbkdf2`]).
+pub trait PasswordKdf<D: Digest> {
+    fn derive(&self, N: &BigNum, salt: &[u8], password: &[u8]) -> BigNum;
+}
+
+/// This crate's original derivation, `H(salt || password) mod N`. Kept as
+/// the default so existing callers are unaffected.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultKdf;
+
+impl<D: Digest> PasswordKdf<D> for DefaultKdf {
+    fn derive(&self, N: &BigNum, salt: &[u8], password: &[u8]) -> BigNum {
+        let mut buffer = Vec::with_capacity(salt.len() + password.len());
+        buffer.extend_from_slice(salt);
+        buffer.extend_from_slice(password);
+        let x: BigNum = deserialize(&D::digest(&buffer));
+        &x % N
+    }
+}
+
+/// A single-block PBKDF2 built on this crate's `hmac`, for slowing down
+/// offline brute-force of a leaked verifier database. `iterations` should
+/// be chosen per current guidance for the digest in use.
+#[derive(Debug, Clone, Copy)]
+pub struct Pbkdf2<D: Digest> {
+    iterations: u32,
+    _digest: PhantomData<D>,
+}
+
+impl<D: Digest> Pbkdf2<D> {
+    pub fn new(iterations: u32) -> Self {
+        Pbkdf2 {
+            iterations,
+            _digest: PhantomData,
+        }
+    }
+}
+
+impl<D: Digest> PasswordKdf<D> for Pbkdf2<D> {
+    fn derive(&self, N: &BigNum, salt: &[u8], password: &[u8]) -> BigNum {
+        let mut salt_with_counter = Vec::with_capacity(salt.len() + 4);
+        salt_with_counter.extend_from_slice(salt);
+        salt_with_counter.extend_from_slice(&1u32.to_be_bytes());
+
+        let mut u = hmac::<D>(password, &salt_with_counter);
+        let mut t = u.clone();
+        for _ in 1..self.iterations.max(1) {
+            u = hmac::<D>(password, &u);
+            for (t_byte, u_byte) in t.iter_mut().zip(u.iter()) {
+                *t_byte ^= u_byte;
+            }
+        }
+
+        let x: BigNum = deserialize(&t);
+        &x % N
+    }
+}
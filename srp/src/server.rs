@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use bignum::NumBigInt as BigNum;
+use sha2::{Sha256, Digest};
+
+use super::{is_congruent_to_zero, DefaultKdf, LoginResult, PasswordKdf, ServerHandshake, SrpError, SRP};
+
+struct UserRecord {
+    salt: Vec<u8>,
+    verifier: BigNum,
+}
+
+/// State kept between [`Server::start_login`] and [`Server::finish_login`]
+/// for one in-progress login: the server's private exponent `b` and the
+/// client's public value `A`. Just enough to rebuild a [`ServerHandshake`]
+/// later — the handshake itself borrows from `SRP`, so it can't be stored
+/// directly alongside it in `Server`.
+struct PendingLogin {
+    A: BigNum,
+    b: BigNum,
+}
+
+/// Registers users by identity and drives end-to-end SRP login, turning
+/// the low-level [`super::ClientHandshake`] / [`super::ServerHandshake`]
+/// primitives into a usable authentication service.
+pub struct Server<D: Digest = Sha256, K: PasswordKdf<D> = DefaultKdf> {
+    srp: SRP<D, K>,
+    users: HashMap<String, UserRecord>,
+    pending: HashMap<String, PendingLogin>,
+}
+
+impl<D: Digest, K: PasswordKdf<D>> Server<D, K> {
+    pub fn new(srp: SRP<D, K>) -> Self {
+        Server {
+            srp,
+            users: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Registers a new user, generating a salt and verifier `v = g^x mod N`.
+    pub fn register(&mut self, identity: &str, password: &[u8]) {
+        let (salt, verifier) = self.srp.password_to_secret(identity.as_bytes(), password);
+        self.users.insert(identity.to_string(), UserRecord { salt, verifier });
+    }
+
+    /// Starts a login for `identity`, spinning up a [`ServerHandshake`]
+    /// keyed to that user and stashing its private exponent until
+    /// [`Server::finish_login`]. Returns the user's salt and the server's
+    /// public value `B`.
+    pub fn start_login(&mut self, identity: &str, A: &BigNum) -> Result<(Vec<u8>, BigNum), SrpError> {
+        let user = self.users.get(identity).ok_or(SrpError::UnknownIdentity)?;
+
+        if is_congruent_to_zero(A, &self.srp.N) {
+            return Err(SrpError::IllegalPublicValue);
+        }
+
+        let handshake = ServerHandshake::new(&self.srp, identity.as_bytes(), &user.salt, &user.verifier);
+        let B = handshake.B().clone();
+        let b = handshake.state.exponent.clone();
+        let salt = user.salt.clone();
+
+        self.pending.insert(identity.to_string(), PendingLogin { A: A.clone(), b });
+        Ok((salt, B))
+    }
+
+    /// Verifies the client's `M1` proof for an in-progress login started
+    /// with [`Server::start_login`], by rebuilding the same [`ServerHandshake`]
+    /// around the stashed exponent and deferring to
+    /// [`ServerHandshake::verify_client`].
+    pub fn finish_login(&mut self, identity: &str, M1: &[u8]) -> LoginResult {
+        let pending = match self.pending.remove(identity) {
+            Some(pending) => pending,
+            None => return LoginResult::Failure,
+        };
+        let user = match self.users.get(identity) {
+            Some(user) => user,
+            None => return LoginResult::Failure,
+        };
+
+        let handshake = ServerHandshake::with_exponent(
+            &self.srp,
+            pending.b,
+            identity.as_bytes(),
+            &user.salt,
+            &user.verifier,
+        );
+        handshake.verify_client(&pending.A, M1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ClientHandshake;
+
+    #[test]
+    fn register_then_login_succeeds() {
+        let mut server = Server::<Sha256>::new(SRP::<Sha256>::new());
+        server.register("alice", b"password123");
+
+        let srp = SRP::<Sha256>::new();
+        let client = ClientHandshake::new(&srp, b"alice", b"password123");
+
+        let (salt, B) = server.start_login("alice", client.A()).unwrap();
+        client.compute_secret(&B, &salt).unwrap();
+        let m1 = client.proof();
+
+        match server.finish_login("alice", &m1) {
+            LoginResult::Success => {}
+            LoginResult::Failure => panic!("login with the correct password failed"),
+        }
+    }
+
+    #[test]
+    fn login_with_wrong_password_fails() {
+        let mut server = Server::<Sha256>::new(SRP::<Sha256>::new());
+        server.register("alice", b"password123");
+
+        let srp = SRP::<Sha256>::new();
+        let client = ClientHandshake::new(&srp, b"alice", b"not-the-password");
+
+        let (salt, B) = server.start_login("alice", client.A()).unwrap();
+        client.compute_secret(&B, &salt).unwrap();
+        let m1 = client.proof();
+
+        match server.finish_login("alice", &m1) {
+            LoginResult::Failure => {}
+            LoginResult::Success => panic!("login with the wrong password succeeded"),
+        }
+    }
+
+    #[test]
+    fn login_for_unregistered_identity_is_rejected() {
+        let mut server = Server::<Sha256>::new(SRP::<Sha256>::new());
+        let srp = SRP::<Sha256>::new();
+        let client = ClientHandshake::new(&srp, b"bob", b"password123");
+
+        assert_eq!(server.start_login("bob", client.A()), Err(SrpError::UnknownIdentity));
+    }
+}